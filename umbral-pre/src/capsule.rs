@@ -211,15 +211,16 @@ impl Capsule {
             lc.push(coeff);
         }
 
+        // There is a minuscule probability that coefficients for two different frags are equal,
+        // in which case we'd rather fail gracefully.
+        let lambdas =
+            lambda_coeffs(&lc).ok_or(OpenReencryptedError::RepeatingCapsuleFrags)?;
+
         let mut e_prime = CurvePoint::identity();
         let mut v_prime = CurvePoint::identity();
-        for (i, cfrag) in cfrags.iter().enumerate() {
-            // There is a minuscule probability that coefficients for two different frags are equal,
-            // in which case we'd rather fail gracefully.
-            let lambda_i =
-                lambda_coeff(&lc, i).ok_or(OpenReencryptedError::RepeatingCapsuleFrags)?;
-            e_prime = &e_prime + &(&cfrag.point_e1 * &lambda_i);
-            v_prime = &v_prime + &(&cfrag.point_v1 * &lambda_i);
+        for (cfrag, lambda_i) in cfrags.iter().zip(lambdas.iter()) {
+            e_prime = &e_prime + &(&cfrag.point_e1 * lambda_i);
+            v_prime = &v_prime + &(&cfrag.point_v1 * lambda_i);
         }
 
         // Secret value 'd' allows to make Umbral non-interactive
@@ -241,16 +242,67 @@ impl Capsule {
     }
 }
 
-fn lambda_coeff(xs: &[NonZeroCurveScalar], i: usize) -> Option<CurveScalar> {
-    let mut res = CurveScalar::one();
-    for j in 0..xs.len() {
-        if j != i {
-            let inv_diff_opt: Option<CurveScalar> = (&xs[j] - &xs[i]).invert().into();
-            let inv_diff = inv_diff_opt?;
-            res = &(&res * &xs[j]) * &inv_diff;
+/// Computes the Lagrange coefficient at zero for every `x_i` in `xs`.
+///
+/// Each coefficient is `Π_{j≠i} x_j · (x_j - x_i)⁻¹`, so a naive implementation
+/// performs `xs.len() - 1` field inversions per coefficient, i.e. `O(n²)`
+/// inversions overall. Since inversion is by far the most expensive scalar
+/// operation, we instead gather every denominator `x_j - x_i` into a single
+/// vector and invert all of them at once with Montgomery's batch-inversion
+/// trick, trading the `O(n²)` inversions for one.
+///
+/// Returns `None` if any two arguments are equal (a zero denominator), which
+/// also makes the batched product zero and so is detected as a failed inversion.
+fn lambda_coeffs(xs: &[NonZeroCurveScalar]) -> Option<Vec<CurveScalar>> {
+    let n = xs.len();
+
+    // Flat vector of all denominators `d_{i,j} = x_j - x_i` for `i`, `j ≠ i`,
+    // laid out in `i`-major order so the inverses can be read back the same way.
+    let mut diffs = Vec::<CurveScalar>::with_capacity(n * n.saturating_sub(1));
+    for i in 0..n {
+        for j in 0..n {
+            if j != i {
+                diffs.push(&xs[j] - &xs[i]);
+            }
         }
     }
-    Some(res)
+
+    // Montgomery batch inversion: prefix products, a single inversion of the
+    // total product, then a backward pass recovering each individual inverse.
+    let mut prefixes = Vec::<CurveScalar>::with_capacity(diffs.len());
+    let mut running = CurveScalar::one();
+    for d in &diffs {
+        prefixes.push(running);
+        running = &running * d;
+    }
+
+    // If any denominator was zero the total product is zero and has no inverse.
+    let inv_total_opt: Option<CurveScalar> = running.invert().into();
+    let mut inv_running = inv_total_opt?;
+
+    let mut inverses = Vec::<CurveScalar>::with_capacity(diffs.len());
+    inverses.resize(diffs.len(), CurveScalar::one());
+    for k in (0..diffs.len()).rev() {
+        inverses[k] = &prefixes[k] * &inv_running;
+        inv_running = &inv_running * &diffs[k];
+    }
+
+    // Reassemble each coefficient from the batched inverses, matching the
+    // `i`-major layout used when collecting the denominators.
+    let mut coeffs = Vec::<CurveScalar>::with_capacity(n);
+    let mut offset = 0;
+    for i in 0..n {
+        let mut res = CurveScalar::one();
+        for j in 0..n {
+            if j != i {
+                res = &(&res * &xs[j]) * &inverses[offset];
+                offset += 1;
+            }
+        }
+        coeffs.push(res);
+    }
+
+    Some(coeffs)
 }
 
 #[cfg(test)]
@@ -352,6 +404,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_open_reencrypted_repeating_cfrags() {
+        let delegating_sk = SecretKey::random();
+        let delegating_pk = delegating_sk.public_key();
+
+        let signer = Signer::new(SecretKey::random());
+
+        let receiving_sk = SecretKey::random();
+        let receiving_pk = receiving_sk.public_key();
+
+        let (capsule, _key_seed) = Capsule::from_public_key(&mut OsRng, &delegating_pk);
+
+        let kfrags = generate_kfrags(&delegating_sk, &receiving_pk, &signer, 2, 3, true, true);
+
+        // Two CapsuleFrags derived from the same KeyFrag hash to the same
+        // Lagrange argument, so they must be rejected as a zero denominator
+        // by the batched inversion, exactly as the naive per-pair version did.
+        let cfrag_a = reencrypt(&capsule, &kfrags[0]).to_unverified();
+        let cfrag_b = reencrypt(&capsule, &kfrags[0]).to_unverified();
+
+        let result = capsule.open_reencrypted(&receiving_sk, &delegating_pk, &[cfrag_a, cfrag_b]);
+        assert_eq!(
+            result.map(|x| x.as_secret().clone()),
+            Err(OpenReencryptedError::RepeatingCapsuleFrags)
+        );
+    }
+
     #[cfg(feature = "serde-support")]
     #[test]
     fn test_serde_serialization() {
@@ -0,0 +1,636 @@
+//! No-dealer distributed generation of the delegating keypair.
+//!
+//! A group of `n` participants jointly produces a single delegating
+//! [`PublicKey`] together with a Shamir sharing of the matching secret key,
+//! such that no participant ever learns the full secret. The resulting group
+//! public key can be used anywhere a `delegating_pk` is expected (for example
+//! [`Capsule::from_public_key`](crate::Capsule), `open_original`/`open_reencrypted`).
+//!
+//! The protocol is a Pedersen-style distributed key generation (SimplPedPoP):
+//! each participant `i` samples a degree `t-1` polynomial `f_i`, publishes
+//! Feldman commitments to its coefficients together with a Schnorr proof of
+//! knowledge of `f_i(0)`, and privately sends every other participant `j` the
+//! evaluation `f_i(j)`. Each `j` checks every received share against the
+//! sender's commitments and aborts, recording the culprit, on any mismatch.
+//! The aggregated secret share is `s_j = Σ_i f_i(j)`, the group public key is
+//! `Σ_i f_i(0)·G`, and the (never materialized) total secret is `Σ_i f_i(0)`.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use generic_array::sequence::{Concat, Split};
+use generic_array::typenum::Unsigned;
+use generic_array::GenericArray;
+use rand_core::{CryptoRng, RngCore};
+use typenum::{op, U4};
+
+use crate::curve::{CurvePoint, CurveScalar, NonZeroCurveScalar};
+use crate::hashing::ScalarDigest;
+use crate::keys::PublicKey;
+use crate::secret_box::SecretBox;
+use crate::traits::{
+    ConstructionError, DeserializableFromArray, HasTypeName, RepresentableAsArray,
+    SerializableToArray,
+};
+
+type PointSize = <CurvePoint as RepresentableAsArray>::Size;
+type ScalarSize = <CurveScalar as RepresentableAsArray>::Size;
+type U32Size = U4;
+
+/// Encodes a `u32` as a fixed-size big-endian array, for embedding indices
+/// alongside points/scalars in the fixed-size message encodings below.
+fn encode_u32(value: u32) -> GenericArray<u8, U32Size> {
+    GenericArray::clone_from_slice(&value.to_be_bytes())
+}
+
+/// Inverse of [`encode_u32`].
+fn decode_u32(arr: GenericArray<u8, U32Size>) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(arr.as_slice());
+    u32::from_be_bytes(bytes)
+}
+
+/// Errors that can happen while running the distributed key generation.
+#[derive(Debug, PartialEq)]
+pub enum DkgError {
+    /// A received share did not match the commitments published by its sender.
+    /// Carries the index of the participant that produced the invalid share.
+    InvalidShare(u32),
+    /// A participant's Schnorr proof of knowledge of `f_i(0)` did not verify.
+    /// Carries the index of the offending participant.
+    InvalidProof(u32),
+    /// The supplied participant index is zero, or the set of contributions is
+    /// inconsistent with the announced parameters.
+    InconsistentParameters,
+}
+
+impl fmt::Display for DkgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidShare(i) => write!(f, "Invalid share received from participant {}", i),
+            Self::InvalidProof(i) => write!(f, "Invalid proof of knowledge from participant {}", i),
+            Self::InconsistentParameters => write!(f, "Inconsistent DKG parameters"),
+        }
+    }
+}
+
+/// Maps a one-based participant index to its polynomial argument.
+fn participant_arg(index: u32) -> NonZeroCurveScalar {
+    // Index 0 is reserved for the secret (`f_i(0)`), so valid participants are
+    // numbered from 1 and always map to a non-zero scalar.
+    NonZeroCurveScalar::from_u64(u64::from(index))
+}
+
+/// A Schnorr proof of knowledge of the secret behind a Feldman commitment's
+/// constant term, i.e. of `f_i(0)` such that `commitments[0] == f_i(0)·G`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProofOfKnowledge {
+    commitment: CurvePoint,
+    response: CurveScalar,
+}
+
+impl RepresentableAsArray for ProofOfKnowledge {
+    type Size = op!(PointSize + ScalarSize);
+}
+
+impl SerializableToArray for ProofOfKnowledge {
+    fn to_array(&self) -> GenericArray<u8, Self::Size> {
+        self.commitment
+            .to_array()
+            .concat(self.response.to_array())
+    }
+}
+
+impl DeserializableFromArray for ProofOfKnowledge {
+    fn from_array(arr: &GenericArray<u8, Self::Size>) -> Result<Self, ConstructionError> {
+        let (commitment, rest) = CurvePoint::take(*arr)?;
+        let response = CurveScalar::take_last(rest)?;
+        Ok(Self {
+            commitment,
+            response,
+        })
+    }
+}
+
+impl HasTypeName for ProofOfKnowledge {
+    fn type_name() -> &'static str {
+        "ProofOfKnowledge"
+    }
+}
+
+impl ProofOfKnowledge {
+    fn new(
+        rng: &mut (impl CryptoRng + RngCore),
+        index: u32,
+        secret: &NonZeroCurveScalar,
+        public: &CurvePoint,
+    ) -> Self {
+        let g = CurvePoint::generator();
+        let k = SecretBox::new(NonZeroCurveScalar::random(rng));
+        let commitment = &g * k.as_secret();
+        let challenge = Self::challenge(index, public, &commitment);
+        let response = k.as_secret().as_ref() + &(secret.as_ref() * &challenge);
+        Self {
+            commitment,
+            response,
+        }
+    }
+
+    fn verify(&self, index: u32, public: &CurvePoint) -> bool {
+        let g = CurvePoint::generator();
+        let challenge = Self::challenge(index, public, &self.commitment);
+        &g * &self.response == &self.commitment + &(public * &challenge)
+    }
+
+    fn challenge(index: u32, public: &CurvePoint, commitment: &CurvePoint) -> CurveScalar {
+        ScalarDigest::new_with_dst(b"DKG_POK")
+            .chain_bytes(index.to_be_bytes())
+            .chain_point(public)
+            .chain_point(commitment)
+            .finalize()
+    }
+}
+
+/// The public message broadcast by a participant in the first round: the
+/// Feldman commitments to the coefficients of `f_i` together with the proof of
+/// knowledge of `f_i(0)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommitmentMessage {
+    pub(crate) index: u32,
+    pub(crate) commitments: Vec<CurvePoint>,
+    pub(crate) proof: ProofOfKnowledge,
+}
+
+impl CommitmentMessage {
+    /// This participant's index.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The Feldman commitments to the coefficients of `f_i`, highest degree last.
+    pub fn commitments(&self) -> &[CurvePoint] {
+        &self.commitments
+    }
+
+    /// The proof of knowledge of `f_i(0)`.
+    pub fn proof(&self) -> &ProofOfKnowledge {
+        &self.proof
+    }
+
+    /// The contribution of this participant to the group public key, `f_i(0)·G`.
+    pub fn public_contribution(&self) -> &CurvePoint {
+        &self.commitments[0]
+    }
+
+    /// Serializes this message for transmission to the other participants.
+    ///
+    /// The commitment vector's length depends on the threshold agreed for the
+    /// run, so unlike [`SerializableToArray`] (which needs a compile-time
+    /// size) the encoding is a length-prefixed byte vector rather than a bare
+    /// concatenation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let point_size = PointSize::to_usize();
+        let proof_size = <ProofOfKnowledge as RepresentableAsArray>::Size::to_usize();
+        let mut out = Vec::with_capacity(8 + self.commitments.len() * point_size + proof_size);
+        out.extend_from_slice(&self.index.to_be_bytes());
+        out.extend_from_slice(&(self.commitments.len() as u32).to_be_bytes());
+        for commitment in &self.commitments {
+            out.extend_from_slice(commitment.to_array().as_slice());
+        }
+        out.extend_from_slice(self.proof.to_array().as_slice());
+        out
+    }
+
+    /// Deserializes a message produced by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConstructionError> {
+        let point_size = PointSize::to_usize();
+        let proof_size = <ProofOfKnowledge as RepresentableAsArray>::Size::to_usize();
+
+        if bytes.len() < 8 {
+            return Err(ConstructionError::new("CommitmentMessage", "Input too short"));
+        }
+        let (header, rest) = bytes.split_at(8);
+        let index = u32::from_be_bytes(header[0..4].try_into().unwrap());
+        let count = u32::from_be_bytes(header[4..8].try_into().unwrap()) as usize;
+
+        if rest.len() != count * point_size + proof_size {
+            return Err(ConstructionError::new(
+                "CommitmentMessage",
+                "Unexpected input length",
+            ));
+        }
+
+        let (points_bytes, proof_bytes) = rest.split_at(count * point_size);
+        let mut commitments = Vec::with_capacity(count);
+        for chunk in points_bytes.chunks(point_size) {
+            let arr = GenericArray::<u8, PointSize>::from_slice(chunk);
+            commitments.push(
+                CurvePoint::from_array(arr)
+                    .map_err(|_| ConstructionError::new("CommitmentMessage", "Invalid commitment"))?,
+            );
+        }
+
+        let proof_arr = GenericArray::<u8, <ProofOfKnowledge as RepresentableAsArray>::Size>::from_slice(
+            proof_bytes,
+        );
+        let proof = ProofOfKnowledge::from_array(proof_arr)
+            .map_err(|_| ConstructionError::new("CommitmentMessage", "Invalid proof"))?;
+
+        Ok(Self {
+            index,
+            commitments,
+            proof,
+        })
+    }
+
+    /// Verifies the proof of knowledge of `f_i(0)`.
+    fn verify_proof(&self) -> Result<(), DkgError> {
+        if self.proof.verify(self.index, &self.commitments[0]) {
+            Ok(())
+        } else {
+            Err(DkgError::InvalidProof(self.index))
+        }
+    }
+
+    /// Evaluates the committed polynomial "in the exponent" at the argument of
+    /// participant `j`, yielding `Σ_k j^k · commitment_k`.
+    fn evaluate_commitments(&self, arg: &NonZeroCurveScalar) -> CurvePoint {
+        let mut acc = CurvePoint::identity();
+        for commitment in self.commitments.iter().rev() {
+            acc = &(&acc * arg.as_ref()) + commitment;
+        }
+        acc
+    }
+}
+
+/// The private share `f_i(j)` that participant `i` sends to participant `j`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShareMessage {
+    pub(crate) sender: u32,
+    pub(crate) receiver: u32,
+    pub(crate) value: CurveScalar,
+}
+
+impl ShareMessage {
+    /// The index of the participant that produced this share.
+    pub fn sender(&self) -> u32 {
+        self.sender
+    }
+
+    /// The index of the participant this share is intended for.
+    pub fn receiver(&self) -> u32 {
+        self.receiver
+    }
+}
+
+impl RepresentableAsArray for ShareMessage {
+    type Size = op!(U32Size + U32Size + ScalarSize);
+}
+
+impl SerializableToArray for ShareMessage {
+    fn to_array(&self) -> GenericArray<u8, Self::Size> {
+        encode_u32(self.sender)
+            .concat(encode_u32(self.receiver))
+            .concat(self.value.to_array())
+    }
+}
+
+impl DeserializableFromArray for ShareMessage {
+    fn from_array(arr: &GenericArray<u8, Self::Size>) -> Result<Self, ConstructionError> {
+        let (sender_arr, rest): (GenericArray<u8, U32Size>, _) = Split::split(*arr);
+        let (receiver_arr, rest): (GenericArray<u8, U32Size>, _) = Split::split(rest);
+        let value = CurveScalar::take_last(rest)?;
+        Ok(Self {
+            sender: decode_u32(sender_arr),
+            receiver: decode_u32(receiver_arr),
+            value,
+        })
+    }
+}
+
+impl HasTypeName for ShareMessage {
+    fn type_name() -> &'static str {
+        "ShareMessage"
+    }
+}
+
+/// The secret polynomial sampled by a single participant, kept private between
+/// the two rounds of the protocol.
+pub struct Contributor {
+    index: u32,
+    threshold: u32,
+    coefficients: Vec<NonZeroCurveScalar>,
+}
+
+impl Contributor {
+    /// Samples a fresh degree `threshold - 1` polynomial for participant
+    /// `index` (one-based), returning the contributor state together with the
+    /// public commitment message to broadcast.
+    pub fn new(
+        rng: &mut (impl CryptoRng + RngCore),
+        index: u32,
+        threshold: u32,
+    ) -> Result<(Self, CommitmentMessage), DkgError> {
+        if index == 0 || threshold == 0 {
+            return Err(DkgError::InconsistentParameters);
+        }
+
+        let g = CurvePoint::generator();
+
+        let coefficients: Vec<NonZeroCurveScalar> = (0..threshold)
+            .map(|_| NonZeroCurveScalar::random(rng))
+            .collect();
+
+        let commitments: Vec<CurvePoint> = coefficients.iter().map(|c| &g * c.as_ref()).collect();
+
+        let proof = ProofOfKnowledge::new(rng, index, &coefficients[0], &commitments[0]);
+
+        let contributor = Self {
+            index,
+            threshold,
+            coefficients,
+        };
+        let message = CommitmentMessage {
+            index,
+            commitments,
+            proof,
+        };
+        Ok((contributor, message))
+    }
+
+    /// Evaluates `f_i(j)` for the given receiver index.
+    pub fn share_for(&self, receiver: u32) -> ShareMessage {
+        let arg = participant_arg(receiver);
+        let mut acc = CurveScalar::zero();
+        for coeff in self.coefficients.iter().rev() {
+            acc = &(&acc * arg.as_ref()) + coeff.as_ref();
+        }
+        ShareMessage {
+            sender: self.index,
+            receiver,
+            value: acc,
+        }
+    }
+
+    /// This participant's own index.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The threshold this contribution was generated for.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+}
+
+/// The finalized output of the DKG for a single participant: its aggregated
+/// secret share and the group public key shared by all participants.
+pub struct DkgOutput {
+    index: u32,
+    share: SecretBox<NonZeroCurveScalar>,
+    group_pk: PublicKey,
+}
+
+impl DkgOutput {
+    /// The aggregated secret share `s_j = Σ_i f_i(j)` held by this participant.
+    pub fn secret_share(&self) -> &SecretBox<NonZeroCurveScalar> {
+        &self.share
+    }
+
+    /// The group public key, usable as a `delegating_pk`.
+    pub fn group_public_key(&self) -> &PublicKey {
+        &self.group_pk
+    }
+
+    /// This participant's index.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+/// Finalizes the DKG for participant `index`.
+///
+/// `threshold` is the threshold agreed for this run; every participant's
+/// commitment message must carry exactly `threshold` Feldman commitments, i.e.
+/// come from a degree `threshold - 1` polynomial. Without this check a
+/// participant could silently publish a higher-degree polynomial and raise the
+/// real reconstruction threshold above the one advertised to the group.
+///
+/// `commitments` holds the broadcast commitment messages from every
+/// participant (including this one), and `shares` the private shares `f_i(index)`
+/// received from each participant `i`. Every proof and every share is verified;
+/// the index of the first offending participant is returned on failure.
+pub fn finalize(
+    index: u32,
+    threshold: u32,
+    commitments: &[CommitmentMessage],
+    shares: &[ShareMessage],
+) -> Result<DkgOutput, DkgError> {
+    if index == 0
+        || threshold == 0
+        || commitments.is_empty()
+        || shares.len() != commitments.len()
+    {
+        return Err(DkgError::InconsistentParameters);
+    }
+
+    if commitments
+        .iter()
+        .any(|message| message.commitments.len() != threshold as usize)
+    {
+        return Err(DkgError::InconsistentParameters);
+    }
+
+    // A participant broadcasting two different messages under the same index
+    // would otherwise get both its public contribution and its share summed
+    // in twice below, silently giving it double weight in the group key.
+    let mut indices: Vec<u32> = commitments.iter().map(|message| message.index).collect();
+    indices.sort_unstable();
+    if indices.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(DkgError::InconsistentParameters);
+    }
+
+    let arg = participant_arg(index);
+
+    let mut aggregated = CurveScalar::zero();
+    let mut group_point = CurvePoint::identity();
+
+    for message in commitments {
+        message.verify_proof()?;
+
+        let share = shares
+            .iter()
+            .find(|s| s.sender == message.index && s.receiver == index)
+            .ok_or(DkgError::InconsistentParameters)?;
+
+        let g = CurvePoint::generator();
+        if &g * &share.value != message.evaluate_commitments(&arg) {
+            return Err(DkgError::InvalidShare(message.index));
+        }
+
+        aggregated = &aggregated + &share.value;
+        group_point = &group_point + message.public_contribution();
+    }
+
+    let share = NonZeroCurveScalar::from_backend_scalar(aggregated)
+        .ok_or(DkgError::InconsistentParameters)?;
+
+    Ok(DkgOutput {
+        index,
+        share: SecretBox::new(share),
+        group_pk: PublicKey::from_point(group_point),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand_core::OsRng;
+
+    use super::{finalize, CommitmentMessage, Contributor, DkgError, ShareMessage};
+
+    use crate::curve::{CurvePoint, CurveScalar, NonZeroCurveScalar};
+    use crate::traits::{DeserializableFromArray, SerializableToArray};
+
+    /// The Lagrange coefficient at zero for `index` over the given index set,
+    /// used here only to check that the shares produced by `finalize`
+    /// reconstruct the group secret.
+    fn lagrange_at_zero(indices: &[u32], index: u32) -> CurveScalar {
+        let xi = NonZeroCurveScalar::from_u64(u64::from(index));
+        let mut res = CurveScalar::one();
+        for &other in indices {
+            if other != index {
+                let xj = NonZeroCurveScalar::from_u64(u64::from(other));
+                let inv_diff: Option<CurveScalar> = (xj.as_ref() - xi.as_ref()).invert().into();
+                res = &(&res * xj.as_ref()) * &inv_diff.unwrap();
+            }
+        }
+        res
+    }
+
+    fn run_dkg(threshold: u32, n: u32) -> (Vec<CommitmentMessage>, Vec<Contributor>) {
+        let mut rng = OsRng;
+        let mut contributors = Vec::new();
+        let mut commitments = Vec::new();
+        for i in 1..=n {
+            let (contributor, message) = Contributor::new(&mut rng, i, threshold).unwrap();
+            contributors.push(contributor);
+            commitments.push(message);
+        }
+        (commitments, contributors)
+    }
+
+    #[test]
+    fn test_finalize_round_trip_reconstructs_secret() {
+        let threshold = 2;
+        let n = 3;
+        let (commitments, contributors) = run_dkg(threshold, n);
+
+        let outputs: Vec<_> = contributors
+            .iter()
+            .map(|contributor| {
+                let shares: Vec<_> = contributors
+                    .iter()
+                    .map(|c| c.share_for(contributor.index()))
+                    .collect();
+                finalize(contributor.index(), threshold, &commitments, &shares).unwrap()
+            })
+            .collect();
+
+        for output in &outputs {
+            assert_eq!(output.group_public_key(), outputs[0].group_public_key());
+        }
+
+        let subset = &outputs[..threshold as usize];
+        let indices: Vec<u32> = subset.iter().map(|o| o.index()).collect();
+
+        let mut secret = CurveScalar::zero();
+        for output in subset {
+            let lambda = lagrange_at_zero(&indices, output.index());
+            secret = &secret + &(&lambda * output.secret_share().as_secret().as_ref());
+        }
+
+        let g = CurvePoint::generator();
+        assert_eq!(&g * &secret, outputs[0].group_public_key().to_point());
+    }
+
+    #[test]
+    fn test_finalize_rejects_wrong_threshold() {
+        let (mut commitments, contributors) = run_dkg(2, 3);
+        // Tamper with one participant's message so it no longer matches the
+        // threshold the rest of the group agreed on.
+        commitments[0].commitments.pop();
+
+        let shares: Vec<_> = contributors
+            .iter()
+            .map(|c| c.share_for(contributors[0].index()))
+            .collect();
+
+        let result = finalize(contributors[0].index(), 2, &commitments, &shares);
+        assert_eq!(result, Err(DkgError::InconsistentParameters));
+    }
+
+    #[test]
+    fn test_finalize_rejects_duplicate_index() {
+        let (mut commitments, contributors) = run_dkg(2, 3);
+        // A participant publishing a second message under an index already
+        // in use must not get its contribution counted twice.
+        let duplicate = commitments[1].clone();
+        commitments.push(duplicate);
+
+        let mut shares: Vec<_> = contributors
+            .iter()
+            .map(|c| c.share_for(contributors[0].index()))
+            .collect();
+        shares.push(shares[1].clone());
+
+        let result = finalize(contributors[0].index(), 2, &commitments, &shares);
+        assert_eq!(result, Err(DkgError::InconsistentParameters));
+    }
+
+    #[test]
+    fn test_finalize_rejects_invalid_proof() {
+        let (mut commitments, contributors) = run_dkg(2, 2);
+        commitments[0].proof.response = &commitments[0].proof.response + &CurveScalar::one();
+
+        let shares: Vec<_> = contributors
+            .iter()
+            .map(|c| c.share_for(contributors[1].index()))
+            .collect();
+
+        let result = finalize(contributors[1].index(), 2, &commitments, &shares);
+        assert_eq!(result, Err(DkgError::InvalidProof(contributors[0].index())));
+    }
+
+    #[test]
+    fn test_finalize_rejects_invalid_share() {
+        let (commitments, contributors) = run_dkg(2, 2);
+
+        let mut shares: Vec<_> = contributors
+            .iter()
+            .map(|c| c.share_for(contributors[1].index()))
+            .collect();
+        shares[0].value = &shares[0].value + &CurveScalar::one();
+
+        let result = finalize(contributors[1].index(), 2, &commitments, &shares);
+        assert_eq!(result, Err(DkgError::InvalidShare(contributors[0].index())));
+    }
+
+    #[test]
+    fn test_commitment_message_byte_round_trip() {
+        let (commitments, _contributors) = run_dkg(3, 1);
+        let bytes = commitments[0].to_bytes();
+        let message_back = CommitmentMessage::from_bytes(&bytes).unwrap();
+        assert_eq!(commitments[0], message_back);
+    }
+
+    #[test]
+    fn test_share_message_array_round_trip() {
+        let (_commitments, contributors) = run_dkg(2, 2);
+        let share = contributors[0].share_for(contributors[1].index());
+        let arr = share.to_array();
+        let share_back = ShareMessage::from_array(&arr).unwrap();
+        assert_eq!(share, share_back);
+    }
+}
@@ -0,0 +1,516 @@
+//! Threshold Schnorr signing (FROST) for the kfrag [`Signer`](crate::Signer).
+//!
+//! Signing kfrags with a single key is a bottleneck: whoever holds the signing
+//! key can forge delegation authorizations on their own. This module implements
+//! a FROST-style two-round threshold Schnorr signature so that any quorum of
+//! `t` signers jointly produces a signature verifiable under a single aggregate
+//! [`PublicKey`], without any of them ever reconstructing the signing key.
+//!
+//! The key material is a Shamir sharing of the signing key, for example the one
+//! produced by the [`dkg`](crate::dkg) subsystem: each signer `i` holds a share
+//! `s_i` and the aggregate public key is `s·G` where `s` is the (never
+//! materialized) secret.
+//!
+//! Round one: each participant samples two nonces `(d_i, e_i)` and publishes the
+//! commitments `(D_i = d_i·G, E_i = e_i·G)`.
+//!
+//! Round two: given the message and the set of participating commitments, each
+//! participant computes the binding factor `ρ_i = H(i, msg, commitments)`, the
+//! group commitment `R = Σ_i (D_i + ρ_i·E_i)`, the challenge
+//! `c = H(R, group_pk, msg)`, and its response
+//! `z_i = d_i + ρ_i·e_i + λ_i·s_i·c`, where `λ_i` is the Lagrange coefficient at
+//! zero over the signing set. The aggregate signature is `(R, Σ_i z_i)`.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use generic_array::sequence::{Concat, Split};
+use generic_array::GenericArray;
+use rand_core::{CryptoRng, RngCore};
+use typenum::{op, U4};
+
+use crate::curve::{CurvePoint, CurveScalar, NonZeroCurveScalar};
+use crate::dkg::DkgOutput;
+use crate::hashing::ScalarDigest;
+use crate::keys::PublicKey;
+use crate::secret_box::SecretBox;
+use crate::traits::{
+    ConstructionError, DeserializableFromArray, HasTypeName, RepresentableAsArray,
+    SerializableToArray,
+};
+
+type PointSize = <CurvePoint as RepresentableAsArray>::Size;
+type ScalarSize = <CurveScalar as RepresentableAsArray>::Size;
+type U32Size = U4;
+
+/// Encodes a `u32` as a fixed-size big-endian array, for embedding indices
+/// alongside points/scalars in the fixed-size message encodings below.
+fn encode_u32(value: u32) -> GenericArray<u8, U32Size> {
+    GenericArray::clone_from_slice(&value.to_be_bytes())
+}
+
+/// Inverse of [`encode_u32`].
+fn decode_u32(arr: GenericArray<u8, U32Size>) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(arr.as_slice());
+    u32::from_be_bytes(bytes)
+}
+
+/// Errors that can happen while running the threshold signing protocol.
+#[derive(Debug, PartialEq)]
+pub enum FrostError {
+    /// The set of participating commitments is empty or has duplicate indices.
+    InvalidSignerSet,
+    /// A round-two response did not come from a participant present in round one.
+    UnknownParticipant(u32),
+    /// The aggregated signature failed verification under the group public key.
+    InvalidSignature,
+}
+
+impl fmt::Display for FrostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSignerSet => write!(f, "Empty or inconsistent signer set"),
+            Self::UnknownParticipant(i) => write!(f, "Response from unknown participant {}", i),
+            Self::InvalidSignature => write!(f, "Aggregated signature failed verification"),
+        }
+    }
+}
+
+/// Maps a one-based signer index to its Lagrange argument.
+fn signer_arg(index: u32) -> NonZeroCurveScalar {
+    NonZeroCurveScalar::from_u64(u64::from(index))
+}
+
+/// The public round-one commitment `(D_i, E_i)` broadcast by a signer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SigningCommitment {
+    pub(crate) index: u32,
+    pub(crate) hiding: CurvePoint,
+    pub(crate) binding: CurvePoint,
+}
+
+impl SigningCommitment {
+    /// The index of the signer that published this commitment.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl RepresentableAsArray for SigningCommitment {
+    type Size = op!(U32Size + PointSize + PointSize);
+}
+
+impl SerializableToArray for SigningCommitment {
+    fn to_array(&self) -> GenericArray<u8, Self::Size> {
+        encode_u32(self.index)
+            .concat(self.hiding.to_array())
+            .concat(self.binding.to_array())
+    }
+}
+
+impl DeserializableFromArray for SigningCommitment {
+    fn from_array(arr: &GenericArray<u8, Self::Size>) -> Result<Self, ConstructionError> {
+        let (index_arr, rest): (GenericArray<u8, U32Size>, _) = Split::split(*arr);
+        let (hiding, rest) = CurvePoint::take(rest)?;
+        let binding = CurvePoint::take_last(rest)?;
+        Ok(Self {
+            index: decode_u32(index_arr),
+            hiding,
+            binding,
+        })
+    }
+}
+
+impl HasTypeName for SigningCommitment {
+    fn type_name() -> &'static str {
+        "SigningCommitment"
+    }
+}
+
+/// The secret nonces kept by a signer between the two rounds.
+pub struct SigningNonces {
+    index: u32,
+    hiding: SecretBox<NonZeroCurveScalar>,
+    binding: SecretBox<NonZeroCurveScalar>,
+}
+
+impl SigningNonces {
+    /// Round one: samples the nonces `(d_i, e_i)` and returns them together
+    /// with the commitment `(D_i, E_i)` to broadcast.
+    pub fn new(
+        rng: &mut (impl CryptoRng + RngCore),
+        index: u32,
+    ) -> (Self, SigningCommitment) {
+        let g = CurvePoint::generator();
+
+        let hiding = SecretBox::new(NonZeroCurveScalar::random(rng));
+        let binding = SecretBox::new(NonZeroCurveScalar::random(rng));
+
+        let commitment = SigningCommitment {
+            index,
+            hiding: &g * hiding.as_secret().as_ref(),
+            binding: &g * binding.as_secret().as_ref(),
+        };
+
+        (
+            Self {
+                index,
+                hiding,
+                binding,
+            },
+            commitment,
+        )
+    }
+}
+
+/// A signer's round-two response `z_i`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SignatureShare {
+    pub(crate) index: u32,
+    pub(crate) value: CurveScalar,
+}
+
+impl SignatureShare {
+    /// The index of the signer that produced this response.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl RepresentableAsArray for SignatureShare {
+    type Size = op!(U32Size + ScalarSize);
+}
+
+impl SerializableToArray for SignatureShare {
+    fn to_array(&self) -> GenericArray<u8, Self::Size> {
+        encode_u32(self.index).concat(self.value.to_array())
+    }
+}
+
+impl DeserializableFromArray for SignatureShare {
+    fn from_array(arr: &GenericArray<u8, Self::Size>) -> Result<Self, ConstructionError> {
+        let (index_arr, rest): (GenericArray<u8, U32Size>, _) = Split::split(*arr);
+        let value = CurveScalar::take_last(rest)?;
+        Ok(Self {
+            index: decode_u32(index_arr),
+            value,
+        })
+    }
+}
+
+impl HasTypeName for SignatureShare {
+    fn type_name() -> &'static str {
+        "SignatureShare"
+    }
+}
+
+/// The aggregate threshold Schnorr signature `(R, z)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThresholdSignature {
+    pub(crate) r: CurvePoint,
+    pub(crate) z: CurveScalar,
+}
+
+impl RepresentableAsArray for ThresholdSignature {
+    type Size = op!(PointSize + ScalarSize);
+}
+
+impl SerializableToArray for ThresholdSignature {
+    fn to_array(&self) -> GenericArray<u8, Self::Size> {
+        self.r.to_array().concat(self.z.to_array())
+    }
+}
+
+impl DeserializableFromArray for ThresholdSignature {
+    fn from_array(arr: &GenericArray<u8, Self::Size>) -> Result<Self, ConstructionError> {
+        let (r, rest) = CurvePoint::take(*arr)?;
+        let z = CurveScalar::take_last(rest)?;
+        Ok(Self { r, z })
+    }
+}
+
+impl HasTypeName for ThresholdSignature {
+    fn type_name() -> &'static str {
+        "ThresholdSignature"
+    }
+}
+
+impl ThresholdSignature {
+    /// Verifies the signature against the aggregate group public key, using the
+    /// standard Schnorr check `z·G == R + c·PK`.
+    pub fn verify(&self, group_pk: &PublicKey, message: &[u8]) -> bool {
+        let g = CurvePoint::generator();
+        let challenge = challenge(&self.r, group_pk, message);
+        &g * &self.z == &self.r + &(&group_pk.to_point() * &challenge)
+    }
+
+    /// Converts this aggregated signature into the crate's standard
+    /// [`Signature`](crate::Signature), the type `generate_kfrags` embeds in
+    /// every `KeyFrag`. This is the integration point between the FROST round
+    /// above and the rest of the crate: once converted, the result is
+    /// verified through `Signature`'s own verification path like any
+    /// single-signer `Signer::sign` output, rather than [`Self::verify`].
+    pub fn into_signature(self) -> crate::Signature {
+        crate::Signature::from_scalars(self.r, self.z)
+    }
+}
+
+/// The binding factor `ρ_i = H(i, msg, commitments)` for a single signer.
+fn binding_factor(index: u32, message: &[u8], commitments: &[SigningCommitment]) -> CurveScalar {
+    let mut digest = ScalarDigest::new_with_dst(b"FROST_RHO")
+        .chain_bytes(index.to_be_bytes())
+        .chain_bytes(message);
+    for commitment in commitments {
+        digest = digest
+            .chain_bytes(commitment.index.to_be_bytes())
+            .chain_point(&commitment.hiding)
+            .chain_point(&commitment.binding);
+    }
+    digest.finalize()
+}
+
+/// The challenge `c = H(R, group_pk, msg)`.
+fn challenge(r: &CurvePoint, group_pk: &PublicKey, message: &[u8]) -> CurveScalar {
+    ScalarDigest::new_with_dst(b"FROST_CHAL")
+        .chain_point(r)
+        .chain_point(&group_pk.to_point())
+        .chain_bytes(message)
+        .finalize()
+}
+
+/// The group commitment `R = Σ_i (D_i + ρ_i·E_i)`.
+fn group_commitment(message: &[u8], commitments: &[SigningCommitment]) -> CurvePoint {
+    let mut r = CurvePoint::identity();
+    for commitment in commitments {
+        let rho = binding_factor(commitment.index, message, commitments);
+        r = &r + &(&commitment.hiding + &(&commitment.binding * &rho));
+    }
+    r
+}
+
+/// Rejects an empty signer set or one with duplicate indices.
+///
+/// A duplicate would desync `R` (computed from every entry in `commitments`,
+/// duplicates included) from the Lagrange coefficients used in [`sign`]
+/// (which treat a duplicate as "not other" and skip it), so this must be
+/// checked up front by both [`sign`] and [`aggregate`].
+fn validate_signer_set(commitments: &[SigningCommitment]) -> Result<(), FrostError> {
+    if commitments.is_empty() {
+        return Err(FrostError::InvalidSignerSet);
+    }
+
+    let mut indices: Vec<u32> = commitments.iter().map(|c| c.index).collect();
+    indices.sort_unstable();
+    if indices.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(FrostError::InvalidSignerSet);
+    }
+
+    Ok(())
+}
+
+/// The Lagrange coefficient at zero for `index` over the given signer set.
+fn lagrange_coeff_at_zero(indices: &[u32], index: u32) -> Option<CurveScalar> {
+    let xi = signer_arg(index);
+    let mut res = CurveScalar::one();
+    for &other in indices {
+        if other != index {
+            let xj = signer_arg(other);
+            let inv_diff: Option<CurveScalar> = (xj.as_ref() - xi.as_ref()).invert().into();
+            res = &(&res * xj.as_ref()) * &inv_diff?;
+        }
+    }
+    Some(res)
+}
+
+/// Round two for a single signer: given its secret nonces, its DKG share, the
+/// message and the full set of participating commitments, produces the response
+/// `z_i = d_i + ρ_i·e_i + λ_i·s_i·c`.
+pub fn sign(
+    nonces: &SigningNonces,
+    output: &DkgOutput,
+    group_pk: &PublicKey,
+    message: &[u8],
+    commitments: &[SigningCommitment],
+) -> Result<SignatureShare, FrostError> {
+    validate_signer_set(commitments)?;
+
+    let indices: Vec<u32> = commitments.iter().map(|c| c.index).collect();
+    let lambda = lagrange_coeff_at_zero(&indices, nonces.index)
+        .ok_or(FrostError::InvalidSignerSet)?;
+
+    let rho = binding_factor(nonces.index, message, commitments);
+    let r = group_commitment(message, commitments);
+    let c = challenge(&r, group_pk, message);
+
+    let value = nonces.hiding.as_secret().as_ref()
+        + &(&(rho * nonces.binding.as_secret().as_ref())
+            + &(&(&lambda * output.secret_share().as_secret().as_ref()) * &c));
+
+    Ok(SignatureShare {
+        index: nonces.index,
+        value,
+    })
+}
+
+/// Aggregates the round-two responses into the final `(R, Σ z_i)` signature and
+/// verifies it against the group public key before returning.
+pub fn aggregate(
+    group_pk: &PublicKey,
+    message: &[u8],
+    commitments: &[SigningCommitment],
+    shares: &[SignatureShare],
+) -> Result<ThresholdSignature, FrostError> {
+    validate_signer_set(commitments)?;
+
+    let r = group_commitment(message, commitments);
+
+    let mut z = CurveScalar::zero();
+    for share in shares {
+        if !commitments.iter().any(|c| c.index == share.index) {
+            return Err(FrostError::UnknownParticipant(share.index));
+        }
+        z = &z + &share.value;
+    }
+
+    let signature = ThresholdSignature { r, z };
+    if signature.verify(group_pk, message) {
+        Ok(signature)
+    } else {
+        Err(FrostError::InvalidSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand_core::OsRng;
+
+    use super::{aggregate, sign, FrostError, SignatureShare, SigningCommitment, SigningNonces};
+
+    use crate::dkg::{finalize, Contributor, DkgOutput};
+    use crate::keys::PublicKey;
+    use crate::traits::{DeserializableFromArray, SerializableToArray};
+
+    /// Runs the DKG for `n` participants with the given `threshold` and
+    /// returns every participant's output, including the shared group key.
+    fn dkg_group(threshold: u32, n: u32) -> Vec<DkgOutput> {
+        let mut rng = OsRng;
+        let mut contributors = Vec::new();
+        let mut commitments = Vec::new();
+        for i in 1..=n {
+            let (contributor, message) = Contributor::new(&mut rng, i, threshold).unwrap();
+            contributors.push(contributor);
+            commitments.push(message);
+        }
+        contributors
+            .iter()
+            .map(|contributor| {
+                let shares: Vec<_> = contributors
+                    .iter()
+                    .map(|c| c.share_for(contributor.index()))
+                    .collect();
+                finalize(contributor.index(), threshold, &commitments, &shares).unwrap()
+            })
+            .collect()
+    }
+
+    fn round_one(outputs: &[DkgOutput]) -> (Vec<SigningNonces>, Vec<SigningCommitment>) {
+        outputs
+            .iter()
+            .map(|output| SigningNonces::new(&mut OsRng, output.index()))
+            .unzip()
+    }
+
+    #[test]
+    fn test_sign_and_aggregate() {
+        let outputs = dkg_group(2, 3);
+        let group_pk: &PublicKey = outputs[0].group_public_key();
+        let message = b"a message to sign";
+
+        let signers = &outputs[..2];
+        let (nonces, commitments) = round_one(signers);
+
+        let shares: Vec<SignatureShare> = nonces
+            .iter()
+            .zip(signers)
+            .map(|(n, output)| sign(n, output, group_pk, message, &commitments).unwrap())
+            .collect();
+
+        let signature = aggregate(group_pk, message, &commitments, &shares).unwrap();
+        assert!(signature.verify(group_pk, message));
+
+        let arr = signature.to_array();
+        let signature_back = super::ThresholdSignature::from_array(&arr).unwrap();
+        assert_eq!(signature, signature_back);
+    }
+
+    #[test]
+    fn test_into_signature_verifies_via_crate_signature() {
+        let outputs = dkg_group(2, 3);
+        let group_pk: &PublicKey = outputs[0].group_public_key();
+        let message = b"embedded in a kfrag";
+
+        let signers = &outputs[..2];
+        let (nonces, commitments) = round_one(signers);
+
+        let shares: Vec<SignatureShare> = nonces
+            .iter()
+            .zip(signers)
+            .map(|(n, output)| sign(n, output, group_pk, message, &commitments).unwrap())
+            .collect();
+
+        let threshold_signature = aggregate(group_pk, message, &commitments, &shares).unwrap();
+        let signature = threshold_signature.into_signature();
+        assert!(signature.verify(group_pk, message));
+    }
+
+    #[test]
+    fn test_sign_rejects_duplicate_signer() {
+        let outputs = dkg_group(2, 3);
+        let group_pk: &PublicKey = outputs[0].group_public_key();
+        let message = b"duplicated signer set";
+
+        let (nonce, commitment) = SigningNonces::new(&mut OsRng, outputs[0].index());
+        let commitments = [commitment, commitment];
+
+        let result = sign(&nonce, &outputs[0], group_pk, message, &commitments);
+        assert_eq!(result, Err(FrostError::InvalidSignerSet));
+    }
+
+    #[test]
+    fn test_aggregate_rejects_duplicate_signer() {
+        let outputs = dkg_group(2, 3);
+        let group_pk: &PublicKey = outputs[0].group_public_key();
+        let message = b"duplicated signer set";
+
+        let (nonce, commitment) = SigningNonces::new(&mut OsRng, outputs[0].index());
+        let share = sign(&nonce, &outputs[0], group_pk, message, &[commitment]).unwrap();
+
+        let result = aggregate(group_pk, message, &[commitment, commitment], &[share]);
+        assert_eq!(result, Err(FrostError::InvalidSignerSet));
+    }
+
+    #[test]
+    fn test_signing_commitment_array_round_trip() {
+        let (_nonce, commitment) = SigningNonces::new(&mut OsRng, 1);
+        let arr = commitment.to_array();
+        let commitment_back = SigningCommitment::from_array(&arr).unwrap();
+        assert_eq!(commitment, commitment_back);
+    }
+
+    #[test]
+    fn test_signature_share_array_round_trip() {
+        let outputs = dkg_group(2, 2);
+        let group_pk: &PublicKey = outputs[0].group_public_key();
+        let message = b"share round trip";
+
+        let (nonces, commitments) = round_one(&outputs);
+        let share = sign(&nonces[0], &outputs[0], group_pk, message, &commitments).unwrap();
+
+        let arr = share.to_array();
+        let share_back = SignatureShare::from_array(&arr).unwrap();
+        assert_eq!(share, share_back);
+    }
+}
@@ -0,0 +1,13 @@
+#![no_std]
+
+extern crate alloc;
+
+pub mod capsule;
+pub mod dkg;
+pub mod frost;
+
+pub use capsule::{Capsule, OpenReencryptedError};
+
+#[cfg(feature = "versioned")]
+#[cfg_attr(docsrs, doc(cfg(feature = "versioned")))]
+pub mod versioned;
@@ -0,0 +1,209 @@
+//! Versioned, self-describing serialization for the crypto objects.
+//!
+//! The plain [`SerializableToArray`] representation is a bare fixed-length
+//! concatenation of points and scalars with no version or type information, so
+//! any future change to the point/scalar layout would silently corrupt stored
+//! data. This module wraps that representation in a small self-describing
+//! header — a magic byte, a per-type tag, and a major/minor version — and
+//! exposes [`to_versioned_bytes`](VersionedSerializable::to_versioned_bytes) /
+//! [`from_versioned_bytes`](VersionedSerializable::from_versioned_bytes) that
+//! validate the header before dispatching to the v1 decoder. The v1 payload
+//! body is exactly the existing `to_array`/`from_array` output, so on-wire data
+//! produced by older versions of the crate can be wrapped without change.
+//!
+//! This path is gated behind the `versioned` feature; embedded users who want
+//! the minimal fixed-length format keep using `to_array`/`from_array` directly.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use generic_array::typenum::Unsigned;
+use generic_array::GenericArray;
+
+use crate::traits::{
+    DeserializableFromArray, HasTypeName, RepresentableAsArray, SerializableToArray,
+};
+
+/// The first byte of every versioned blob, identifying it as umbral output.
+const MAGIC: u8 = 0xA1;
+/// The major/minor version of the serialization format emitted by this build.
+const MAJOR: u8 = 1;
+const MINOR: u8 = 0;
+/// Length of the header prepended to the payload body.
+const HEADER_SIZE: usize = 4;
+
+/// Errors that can happen when decoding a versioned blob.
+#[derive(Debug, PartialEq)]
+pub enum VersionedError {
+    /// The input is shorter than the mandatory header.
+    Truncated,
+    /// The leading magic byte did not match.
+    InvalidMagic,
+    /// The type tag did not match the type being decoded.
+    TypeMismatch,
+    /// The major version is not understood by this build.
+    UnknownVersion {
+        /// The major version read from the header.
+        major: u8,
+        /// The minor version read from the header.
+        minor: u8,
+    },
+    /// The header was valid but the payload body could not be decoded.
+    InvalidBody,
+}
+
+impl fmt::Display for VersionedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "Input is too short to contain a version header"),
+            Self::InvalidMagic => write!(f, "Invalid magic byte"),
+            Self::TypeMismatch => write!(f, "Type tag does not match the expected type"),
+            Self::UnknownVersion { major, minor } => {
+                write!(f, "Unknown serialization version {}.{}", major, minor)
+            }
+            Self::InvalidBody => write!(f, "Payload body could not be decoded"),
+        }
+    }
+}
+
+/// A one-byte tag derived from the type name, used to reject blobs decoded as
+/// the wrong type. It does not need to be collision-free — it only guards
+/// against accidental type confusion of otherwise same-length payloads.
+fn type_tag<T: HasTypeName>() -> u8 {
+    T::type_name()
+        .bytes()
+        .fold(0u8, |acc, b| acc.wrapping_add(b).rotate_left(1))
+}
+
+/// Versioned counterpart to [`SerializableToArray`]/[`DeserializableFromArray`].
+///
+/// Blanket-implemented for every serializable crypto object, so [`Capsule`](crate::Capsule)
+/// and the frags gain the versioned path without per-type boilerplate.
+pub trait VersionedSerializable:
+    SerializableToArray + DeserializableFromArray + HasTypeName
+{
+    /// Serializes `self` with a self-describing version header prepended.
+    fn to_versioned_bytes(&self) -> Vec<u8> {
+        let body = self.to_array();
+        let mut out = Vec::with_capacity(HEADER_SIZE + body.len());
+        out.push(MAGIC);
+        out.push(type_tag::<Self>());
+        out.push(MAJOR);
+        out.push(MINOR);
+        out.extend_from_slice(body.as_slice());
+        out
+    }
+
+    /// Validates the version header and decodes the payload body.
+    fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, VersionedError> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(VersionedError::Truncated);
+        }
+        let (header, body) = bytes.split_at(HEADER_SIZE);
+
+        if header[0] != MAGIC {
+            return Err(VersionedError::InvalidMagic);
+        }
+        if header[1] != type_tag::<Self>() {
+            return Err(VersionedError::TypeMismatch);
+        }
+        if header[2] != MAJOR {
+            return Err(VersionedError::UnknownVersion {
+                major: header[2],
+                minor: header[3],
+            });
+        }
+
+        // v1 bodies are exactly the `to_array` output, so length must match.
+        let expected = <Self as RepresentableAsArray>::Size::to_usize();
+        if body.len() != expected {
+            return Err(VersionedError::InvalidBody);
+        }
+        let arr = GenericArray::<u8, Self::Size>::from_slice(body);
+        Self::from_array(arr).map_err(|_| VersionedError::InvalidBody)
+    }
+}
+
+impl<T> VersionedSerializable for T where
+    T: SerializableToArray + DeserializableFromArray + HasTypeName
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::OsRng;
+
+    use super::{VersionedError, VersionedSerializable, HEADER_SIZE};
+
+    use crate::capsule::Capsule;
+    use crate::frost::{SigningCommitment, SigningNonces};
+    use crate::keys::SecretKey;
+
+    fn sample_commitment() -> SigningCommitment {
+        let (_nonce, commitment) = SigningNonces::new(&mut OsRng, 1);
+        commitment
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let commitment = sample_commitment();
+        let bytes = commitment.to_versioned_bytes();
+        let commitment_back = SigningCommitment::from_versioned_bytes(&bytes).unwrap();
+        assert_eq!(commitment, commitment_back);
+    }
+
+    #[test]
+    fn test_capsule_round_trip() {
+        // `Capsule` is the type named explicitly in the request and the one
+        // with the strongest motivation for a versioned format: capsules are
+        // the thing actually persisted long-term across crate upgrades.
+        let delegating_sk = SecretKey::random();
+        let delegating_pk = delegating_sk.public_key();
+        let (capsule, _key_seed) = Capsule::from_public_key(&mut OsRng, &delegating_pk);
+
+        let bytes = capsule.to_versioned_bytes();
+        let capsule_back = Capsule::from_versioned_bytes(&bytes).unwrap();
+        assert_eq!(capsule, capsule_back);
+    }
+
+    #[test]
+    fn test_truncated() {
+        let commitment = sample_commitment();
+        let bytes = commitment.to_versioned_bytes();
+        let result = SigningCommitment::from_versioned_bytes(&bytes[..HEADER_SIZE - 1]);
+        assert_eq!(result, Err(VersionedError::Truncated));
+    }
+
+    #[test]
+    fn test_invalid_magic() {
+        let commitment = sample_commitment();
+        let mut bytes = commitment.to_versioned_bytes();
+        bytes[0] = 0xFF;
+        let result = SigningCommitment::from_versioned_bytes(&bytes);
+        assert_eq!(result, Err(VersionedError::InvalidMagic));
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let commitment = sample_commitment();
+        let mut bytes = commitment.to_versioned_bytes();
+        bytes[1] = bytes[1].wrapping_add(1);
+        let result = SigningCommitment::from_versioned_bytes(&bytes);
+        assert_eq!(result, Err(VersionedError::TypeMismatch));
+    }
+
+    #[test]
+    fn test_unknown_version() {
+        let commitment = sample_commitment();
+        let mut bytes = commitment.to_versioned_bytes();
+        bytes[2] = bytes[2].wrapping_add(1);
+        let result = SigningCommitment::from_versioned_bytes(&bytes);
+        assert_eq!(
+            result,
+            Err(VersionedError::UnknownVersion {
+                major: bytes[2],
+                minor: bytes[3],
+            })
+        );
+    }
+}